@@ -0,0 +1,53 @@
+//! Pool value-gamma and closed-form LVR (loss-versus-rebalancing).
+//!
+//! An LP position is short gamma: as the fair price moves, the pool's value
+//! trails what a continuously-rebalanced portfolio would hold. The
+//! instantaneous LVR rate is `ℓ(p) = ½·σ²·p²·Γ(p)`, where `Γ(p) = -V''(p)` is
+//! the pool-value gamma at price `p`.
+
+use crate::amm::CFMM;
+
+/// Pools that expose a closed-form value-gamma so LVR can be computed
+/// without finite-differencing `V(p)`.
+pub trait PoolGamma {
+    /// Pool-value gamma `Γ(p) = -V''(p)` at the given fair price.
+    fn gamma(&self, price: f64) -> f64;
+}
+
+impl PoolGamma for CFMM {
+    /// For constant product, `V(p) = 2√(k·p)` with `k = x·y`, so
+    /// `Γ(p) = √k / (2·p^(3/2))`.
+    fn gamma(&self, price: f64) -> f64 {
+        let (rx, ry) = self.reserves();
+        let k = rx * ry;
+        k.sqrt() / (2.0 * price.powf(1.5))
+    }
+}
+
+/// Instantaneous LVR rate `ℓ(p) = ½·σ²·p²·Γ(p)` for a pool exposing `PoolGamma`.
+pub fn instantaneous_lvr(pool: &impl PoolGamma, price: f64, sigma: f64) -> f64 {
+    0.5 * sigma * sigma * price * price * pool.gamma(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_product_lvr_matches_value_formula() {
+        // For constant product, ℓ(p) = (σ²/8)·V(p) with V(p) = 2√(k·p).
+        let rx = 1000.0;
+        let ry = 1000.0;
+        let k = rx * ry;
+        let price = 1.0;
+        let sigma = 0.5;
+
+        let gamma = k.sqrt() / (2.0 * price.powf(1.5));
+        let lvr = 0.5 * sigma * sigma * price * price * gamma;
+
+        let v = 2.0 * (k * price).sqrt();
+        let expected = (sigma * sigma / 8.0) * v;
+
+        assert!((lvr - expected).abs() < 1e-9);
+    }
+}
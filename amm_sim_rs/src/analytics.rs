@@ -0,0 +1,152 @@
+//! Per-strategy performance analytics over the PnL path.
+//!
+//! Complements the terminal `pnl` already reported on `LightweightSimResult`
+//! with risk-adjusted metrics computed from the per-step PnL series, so
+//! scoring isn't dominated by a strategy that simply took on more variance.
+
+use std::collections::HashMap;
+
+use crate::types::result::LightweightStepResult;
+
+/// Risk-adjusted performance metrics for a single strategy's PnL path.
+#[derive(Debug, Clone)]
+pub struct StrategyMetrics {
+    pub annualized_return: f64,
+    pub annualized_volatility: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    pub max_drawdown: f64,
+    pub max_drawdown_duration: u32,
+    pub turnover: f64,
+}
+
+/// Compute `StrategyMetrics` for every strategy from the simulation's
+/// per-step PnL path, annualizing by `gbm_dt` (the step size as a fraction
+/// of a year).
+pub fn compute_strategy_metrics(
+    steps: &[LightweightStepResult],
+    names: &[String],
+    gbm_dt: f64,
+    arb_volume_y: &HashMap<String, f64>,
+    retail_volume_y: &HashMap<String, f64>,
+) -> HashMap<String, StrategyMetrics> {
+    let steps_per_year = 1.0 / gbm_dt;
+
+    names
+        .iter()
+        .map(|name| {
+            let pnl_path: Vec<f64> = steps.iter().map(|s| *s.pnls.get(name).unwrap_or(&0.0)).collect();
+            let returns = step_returns(&pnl_path);
+
+            let mean_return = mean(&returns);
+            let annualized_return = mean_return * steps_per_year;
+            let annualized_volatility = std_dev(&returns, mean_return) * steps_per_year.sqrt();
+
+            let sharpe_ratio = if annualized_volatility > 0.0 {
+                annualized_return / annualized_volatility
+            } else {
+                0.0
+            };
+
+            let annualized_downside_dev = downside_deviation(&returns, mean_return) * steps_per_year.sqrt();
+            let sortino_ratio = if annualized_downside_dev > 0.0 {
+                annualized_return / annualized_downside_dev
+            } else {
+                0.0
+            };
+
+            let (max_drawdown, max_drawdown_duration) = drawdown(&pnl_path);
+            let turnover = arb_volume_y.get(name).unwrap_or(&0.0) + retail_volume_y.get(name).unwrap_or(&0.0);
+
+            (
+                name.clone(),
+                StrategyMetrics {
+                    annualized_return,
+                    annualized_volatility,
+                    sharpe_ratio,
+                    sortino_ratio,
+                    max_drawdown,
+                    max_drawdown_duration,
+                    turnover,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Step-over-step PnL differences.
+fn step_returns(pnl_path: &[f64]) -> Vec<f64> {
+    pnl_path.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+fn mean(xs: &[f64]) -> f64 {
+    if xs.is_empty() {
+        0.0
+    } else {
+        xs.iter().sum::<f64>() / xs.len() as f64
+    }
+}
+
+fn std_dev(xs: &[f64], mean_x: f64) -> f64 {
+    if xs.is_empty() {
+        return 0.0;
+    }
+    let variance = xs.iter().map(|x| (x - mean_x).powi(2)).sum::<f64>() / xs.len() as f64;
+    variance.sqrt()
+}
+
+/// Root-mean-square shortfall below `target`, averaged over all returns
+/// (returns at or above `target` count as zero): the Sortino ratio's
+/// denominator.
+fn downside_deviation(xs: &[f64], target: f64) -> f64 {
+    if xs.is_empty() {
+        return 0.0;
+    }
+    let downside_sq_sum: f64 = xs.iter().map(|x| (x - target).min(0.0).powi(2)).sum();
+    (downside_sq_sum / xs.len() as f64).sqrt()
+}
+
+/// Maximum peak-to-trough decline over `pnl_path`, and how many steps
+/// elapsed between the peak and the trough.
+fn drawdown(pnl_path: &[f64]) -> (f64, u32) {
+    let mut peak = f64::NEG_INFINITY;
+    let mut peak_idx = 0usize;
+    let mut max_dd = 0.0;
+    let mut max_dd_duration = 0u32;
+
+    for (i, &value) in pnl_path.iter().enumerate() {
+        if value > peak {
+            peak = value;
+            peak_idx = i;
+        }
+        let dd = peak - value;
+        if dd > max_dd {
+            max_dd = dd;
+            max_dd_duration = (i - peak_idx) as u32;
+        }
+    }
+
+    (max_dd, max_dd_duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drawdown_tracks_peak_to_trough() {
+        let path = vec![0.0, 10.0, 4.0, 2.0, 8.0, 12.0];
+        let (max_dd, duration) = drawdown(&path);
+        assert!((max_dd - 8.0).abs() < 1e-9);
+        assert_eq!(duration, 2);
+    }
+
+    #[test]
+    fn test_sortino_ignores_upside_deviation() {
+        let returns = vec![5.0, -2.0, 5.0, -2.0];
+        let mean_return = mean(&returns);
+        let downside = downside_deviation(&returns, mean_return);
+        assert!(downside > 0.0);
+        assert!(downside < std_dev(&returns, mean_return));
+    }
+}
@@ -1,6 +1,18 @@
 //! Arbitrageur logic for extracting profit from mispriced AMMs.
 
+use crate::amm::concentrated::ConcentratedAMM;
 use crate::amm::CFMM;
+use crate::market::orderbook::{OrderBook, Side};
+use crate::simulation::engine::SimulationError;
+use crate::types::fixed::Fixed64;
+
+/// Either pool type an [`Arbitrageur`] can trade against.
+pub enum Pool<'a> {
+    /// A full-range constant-product AMM.
+    ConstantProduct(&'a mut CFMM),
+    /// A concentrated-liquidity (Uniswap-v3 style) AMM.
+    Concentrated(&'a mut ConcentratedAMM),
+}
 
 /// Result of an arbitrage attempt.
 #[derive(Debug, Clone)]
@@ -23,16 +35,46 @@ pub struct ArbResult {
 /// For reserves (x, y), k=xy, fee f, and fair price p:
 /// - Buy X from AMM: Δx = x - sqrt(k*(1+f)/p) (profit-maximizing)
 /// - Sell X to AMM: Δx = sqrt(k*(1-f)/p) - x (profit-maximizing)
-pub struct Arbitrageur;
+///
+/// The optimal-size computation runs in checked `Fixed64` arithmetic so that
+/// a given `seed` replays to bit-identical trade sizes on any machine; an
+/// overflow surfaces as `SimulationError::Overflow` instead of `inf`/`NaN`.
+///
+/// Trades whose Y notional falls below `min_trade_size_y` are dropped as
+/// dust rather than executed, so GBM noise near the spot price doesn't
+/// generate a long tail of sub-economic fills that distort volume and
+/// markout statistics.
+pub struct Arbitrageur {
+    min_trade_size_y: f64,
+}
 
 impl Arbitrageur {
-    /// Create a new arbitrageur.
-    pub fn new() -> Self {
-        Self
+    /// Create a new arbitrageur that skips trades below `min_trade_size_y`
+    /// of Y notional.
+    pub fn new(min_trade_size_y: f64) -> Self {
+        Self { min_trade_size_y }
     }
 
-    /// Find and execute the optimal arbitrage trade.
-    pub fn execute_arb(&self, amm: &mut CFMM, fair_price: f64, timestamp: u64) -> Option<ArbResult> {
+    /// Find and execute the optimal arbitrage trade against either pool type.
+    pub fn execute_arb(
+        &self,
+        pool: Pool<'_>,
+        fair_price: f64,
+        timestamp: u64,
+    ) -> Result<Option<ArbResult>, SimulationError> {
+        match pool {
+            Pool::ConstantProduct(amm) => self.execute_arb_cfmm(amm, fair_price, timestamp),
+            Pool::Concentrated(pool) => Ok(self.execute_arb_concentrated(pool, fair_price)),
+        }
+    }
+
+    /// Find and execute the optimal arbitrage trade against a `CFMM`.
+    fn execute_arb_cfmm(
+        &self,
+        amm: &mut CFMM,
+        fair_price: f64,
+        timestamp: u64,
+    ) -> Result<Option<ArbResult>, SimulationError> {
         let (rx, ry) = amm.reserves();
         let spot_price = ry / rx;
 
@@ -43,25 +85,83 @@ impl Arbitrageur {
             // AMM overprices X - sell X to AMM (AMM buys X)
             self.compute_sell_arb(amm, fair_price, timestamp)
         } else {
-            None
+            Ok(None)
         }
     }
 
-    /// Compute and execute optimal trade when buying X from AMM.
+    /// Find and execute the optimal arbitrage trade against a concentrated
+    /// liquidity pool, clamping at range boundaries and walking to the next
+    /// position when the fair price lies beyond the currently active range.
+    ///
+    /// The unconstrained optimal trade moves the pool's marginal price to
+    /// `fair_price` net of the fee wedge; [`ConcentratedAMM::execute_to_price`]
+    /// performs the multi-range walk and sums the filled amounts.
+    fn execute_arb_concentrated(&self, pool: &mut ConcentratedAMM, fair_price: f64) -> Option<ArbResult> {
+        let spot = pool.current_price();
+        if spot == fair_price {
+            return None;
+        }
+
+        let target = if spot < fair_price {
+            fair_price / (1.0 + pool.fee)
+        } else {
+            fair_price * (1.0 - pool.fee)
+        };
+
+        // Walk a copy first so a dust or unprofitable fill never moves the pool.
+        let mut trial = pool.clone();
+        let (filled_x, filled_y) = trial.execute_to_price(target);
+        if filled_y.abs() < self.min_trade_size_y {
+            return None;
+        }
+
+        let side = if filled_x > 0.0 { "sell" } else { "buy" };
+        let profit = if filled_x > 0.0 {
+            filled_x * fair_price - filled_y
+        } else {
+            filled_y.abs() - filled_x.abs() * fair_price
+        };
+
+        if profit <= 0.0 {
+            return None;
+        }
+
+        *pool = trial;
+        Some(ArbResult {
+            amm_name: pool.name.clone(),
+            profit,
+            side,
+            amount_x: filled_x.abs(),
+            amount_y: filled_y.abs(),
+        })
+    }
+
+    /// Quote the optimal buy-X-from-AMM trade without executing it.
     ///
     /// Maximize profit = Δx * p - Y_paid
-    /// Closed-form: Δx = x - sqrt(k*(1+f)/p)
-    fn compute_buy_arb(&self, amm: &mut CFMM, fair_price: f64, timestamp: u64) -> Option<ArbResult> {
+    /// Closed-form: Δx = x - sqrt(k*(1+f)/p), evaluated in `Fixed64` so the
+    /// sizing step is deterministic across platforms. Returns
+    /// `(amount_x, total_y)` if a profitable trade exists.
+    fn quote_buy_arb(&self, amm: &CFMM, fair_price: f64) -> Result<Option<(f64, f64)>, SimulationError> {
         let (rx, ry) = amm.reserves();
-        let k = rx * ry;
         let fee = amm.fees().ask_fee.to_f64();
 
+        let rx_fx = Fixed64::from_f64(rx);
+        let ry_fx = Fixed64::from_f64(ry);
+        let k_fx = rx_fx.checked_mul(ry_fx)?;
+        let fair_price_fx = Fixed64::from_f64(fair_price);
+        let one_plus_fee_fx = Fixed64::from_f64(1.0 + fee);
+
         // Optimal trade size
-        let new_x = (k * (1.0 + fee) / fair_price).sqrt();
-        let amount_x = rx - new_x;
+        let new_x_fx = k_fx
+            .checked_mul(one_plus_fee_fx)?
+            .checked_div(fair_price_fx)?
+            .checked_sqrt()?;
+        let amount_x_fx = rx_fx.checked_sub(new_x_fx)?;
+        let amount_x = amount_x_fx.to_f64();
 
         if amount_x <= 0.0 {
-            return None;
+            return Ok(None);
         }
 
         // Cap at 99% of reserves
@@ -69,82 +169,207 @@ impl Arbitrageur {
 
         // Use fast quote to compute profit
         let (total_y, _) = amm.quote_sell_x(amount_x);
-        if total_y <= 0.0 {
-            return None;
+        if total_y < self.min_trade_size_y {
+            return Ok(None);
         }
 
         // Profit = value of X at fair price - Y paid
         let profit = amount_x * fair_price - total_y;
-
         if profit <= 0.0 {
-            return None;
+            return Ok(None);
         }
 
-        // Execute the trade
-        let _trade = amm.execute_sell_x(amount_x, timestamp)?;
+        Ok(Some((amount_x, total_y)))
+    }
 
-        Some(ArbResult {
+    /// Compute and execute optimal trade when buying X from AMM.
+    fn compute_buy_arb(
+        &self,
+        amm: &mut CFMM,
+        fair_price: f64,
+        timestamp: u64,
+    ) -> Result<Option<ArbResult>, SimulationError> {
+        let Some((amount_x, total_y)) = self.quote_buy_arb(amm, fair_price)? else {
+            return Ok(None);
+        };
+        let profit = amount_x * fair_price - total_y;
+
+        let Some(_trade) = amm.execute_sell_x(amount_x, timestamp) else {
+            return Ok(None);
+        };
+
+        Ok(Some(ArbResult {
             amm_name: amm.name.clone(),
             profit,
             side: "sell", // AMM sells X
             amount_x,
             amount_y: total_y,
-        })
+        }))
     }
 
-    /// Compute and execute optimal trade when selling X to AMM.
+    /// Quote the optimal sell-X-to-AMM trade without executing it.
     ///
     /// Maximize profit = Y_received - Δx * p
-    /// Closed-form: Δx = sqrt(k*(1-f)/p) - x
-    fn compute_sell_arb(&self, amm: &mut CFMM, fair_price: f64, timestamp: u64) -> Option<ArbResult> {
+    /// Closed-form: Δx = sqrt(k*(1-f)/p) - x, evaluated in `Fixed64` so the
+    /// sizing step is deterministic across platforms. Returns
+    /// `(amount_x, y_out)` if a profitable trade exists.
+    fn quote_sell_arb(&self, amm: &CFMM, fair_price: f64) -> Result<Option<(f64, f64)>, SimulationError> {
         let (rx, ry) = amm.reserves();
-        let k = rx * ry;
         let fee = amm.fees().bid_fee.to_f64();
 
+        let rx_fx = Fixed64::from_f64(rx);
+        let ry_fx = Fixed64::from_f64(ry);
+        let k_fx = rx_fx.checked_mul(ry_fx)?;
+        let fair_price_fx = Fixed64::from_f64(fair_price);
+        let one_minus_fee_fx = Fixed64::from_f64(1.0 - fee);
+
         // Optimal trade size
-        let new_x = (k * (1.0 - fee) / fair_price).sqrt();
-        let amount_x = new_x - rx;
+        let new_x_fx = k_fx
+            .checked_mul(one_minus_fee_fx)?
+            .checked_div(fair_price_fx)?
+            .checked_sqrt()?;
+        let amount_x_fx = new_x_fx.checked_sub(rx_fx)?;
+        let amount_x = amount_x_fx.to_f64();
 
         if amount_x <= 0.0 {
-            return None;
+            return Ok(None);
         }
 
         // Use fast quote to compute profit
         let (y_out, _) = amm.quote_buy_x(amount_x);
-        if y_out <= 0.0 {
-            return None;
+        if y_out < self.min_trade_size_y {
+            return Ok(None);
         }
 
         // Profit = Y received - cost of X at fair price
         let profit = y_out - amount_x * fair_price;
-
         if profit <= 0.0 {
-            return None;
+            return Ok(None);
         }
 
-        // Execute the trade
-        let _trade = amm.execute_buy_x(amount_x, timestamp)?;
+        Ok(Some((amount_x, y_out)))
+    }
 
-        Some(ArbResult {
+    /// Compute and execute optimal trade when selling X to AMM.
+    fn compute_sell_arb(
+        &self,
+        amm: &mut CFMM,
+        fair_price: f64,
+        timestamp: u64,
+    ) -> Result<Option<ArbResult>, SimulationError> {
+        let Some((amount_x, y_out)) = self.quote_sell_arb(amm, fair_price)? else {
+            return Ok(None);
+        };
+        let profit = y_out - amount_x * fair_price;
+
+        let Some(_trade) = amm.execute_buy_x(amount_x, timestamp) else {
+            return Ok(None);
+        };
+
+        Ok(Some(ArbResult {
             amm_name: amm.name.clone(),
             profit,
             side: "buy", // AMM buys X
             amount_x,
             amount_y: y_out,
-        })
+        }))
     }
 
-    /// Execute arbitrage on multiple AMMs.
-    pub fn arbitrage_all(&self, amms: &mut [CFMM], fair_price: f64, timestamp: u64) -> Vec<ArbResult> {
+    /// Arb an AMM against the order book when the AMM's spot price diverges
+    /// from the book's mid price: buy on whichever venue is cheaper and sell
+    /// on the other. Both legs are quoted (not executed) first so a
+    /// combination that turns out unprofitable never mutates AMM reserves or
+    /// consumes book depth.
+    pub fn execute_arb_vs_book(
+        &self,
+        amm: &mut CFMM,
+        book: &mut OrderBook,
+        timestamp: u64,
+    ) -> Result<Option<ArbResult>, SimulationError> {
+        let Some(book_mid) = book.mid_price() else {
+            return Ok(None);
+        };
+
+        let (rx, ry) = amm.reserves();
+        let amm_spot = ry / rx;
+
+        if (amm_spot - book_mid).abs() < f64::EPSILON {
+            return Ok(None);
+        }
+
+        if amm_spot < book_mid {
+            // AMM underprices X relative to the book: buy X from the AMM,
+            // sell it into the book's bid.
+            let Some((amount_x, amm_cost)) = self.quote_buy_arb(amm, book_mid)? else {
+                return Ok(None);
+            };
+            let (filled_x, book_proceeds) = book.quote_market_order(Side::Sell, amount_x);
+            if filled_x < amount_x {
+                return Ok(None);
+            }
+            let profit = book_proceeds - amm_cost;
+            if profit <= 0.0 {
+                return Ok(None);
+            }
+
+            let Some(_trade) = amm.execute_sell_x(amount_x, timestamp) else {
+                return Ok(None);
+            };
+            book.match_market_order(Side::Sell, amount_x);
+
+            Ok(Some(ArbResult {
+                amm_name: amm.name.clone(),
+                profit,
+                side: "sell",
+                amount_x,
+                amount_y: amm_cost,
+            }))
+        } else {
+            // AMM overprices X relative to the book: buy X from the book,
+            // sell it into the AMM.
+            let Some((amount_x, amm_proceeds)) = self.quote_sell_arb(amm, book_mid)? else {
+                return Ok(None);
+            };
+            let (filled_x, book_cost) = book.quote_market_order(Side::Buy, amount_x);
+            if filled_x < amount_x {
+                return Ok(None);
+            }
+            let profit = amm_proceeds - book_cost;
+            if profit <= 0.0 {
+                return Ok(None);
+            }
+
+            let Some(_trade) = amm.execute_buy_x(amount_x, timestamp) else {
+                return Ok(None);
+            };
+            book.match_market_order(Side::Buy, amount_x);
+
+            Ok(Some(ArbResult {
+                amm_name: amm.name.clone(),
+                profit,
+                side: "buy",
+                amount_x,
+                amount_y: amm_proceeds,
+            }))
+        }
+    }
+
+    /// Execute arbitrage on multiple constant-product AMMs.
+    pub fn arbitrage_all(
+        &self,
+        amms: &mut [CFMM],
+        fair_price: f64,
+        timestamp: u64,
+    ) -> Result<Vec<ArbResult>, SimulationError> {
         amms.iter_mut()
-            .filter_map(|amm| self.execute_arb(amm, fair_price, timestamp))
+            .filter_map(|amm| self.execute_arb_cfmm(amm, fair_price, timestamp).transpose())
             .collect()
     }
 }
 
 impl Default for Arbitrageur {
     fn default() -> Self {
-        Self::new()
+        Self::new(0.0)
     }
 }
 
@@ -172,4 +397,29 @@ mod tests {
         let amount_x = new_x - rx;
         assert!(amount_x > 0.0); // Should want to sell X
     }
+
+    #[test]
+    fn test_fixed_point_sizing_matches_f64_closed_form() {
+        let rx = 1000.0;
+        let ry = 1000.0;
+        let fair_price = 1.1;
+        let fee = 0.0025;
+
+        let k = rx * ry;
+        let expected = rx - (k * (1.0 + fee) / fair_price).sqrt();
+
+        let rx_fx = Fixed64::from_f64(rx);
+        let ry_fx = Fixed64::from_f64(ry);
+        let k_fx = rx_fx.checked_mul(ry_fx).unwrap();
+        let new_x_fx = k_fx
+            .checked_mul(Fixed64::from_f64(1.0 + fee))
+            .unwrap()
+            .checked_div(Fixed64::from_f64(fair_price))
+            .unwrap()
+            .checked_sqrt()
+            .unwrap();
+        let actual = rx_fx.checked_sub(new_x_fx).unwrap().to_f64();
+
+        assert!((actual - expected).abs() < 1e-6);
+    }
 }
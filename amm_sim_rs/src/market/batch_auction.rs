@@ -0,0 +1,156 @@
+//! Batch-auction routing: clear a step's net retail demand against the
+//! combined AMM liquidity at a single uniform price.
+//!
+//! Sequential per-order routing gives an ordering advantage to whichever
+//! AMM is quoted first within a step. Batch auctions remove that advantage
+//! by aggregating all orders into net buy/sell demand, solving for the one
+//! price at which every pool's post-trade marginal price agrees, and sizing
+//! each pool's fill to hit exactly that price - the same closed-form each
+//! pool's arbitrageur uses to size a trade to a target price (see
+//! [`Arbitrageur::quote_buy_arb`](crate::market::arbitrageur::Arbitrageur),
+//! found here via bisection since the target price itself is the unknown).
+//! Per-pool fills still report each pool's own realized quote, so the
+//! uniform price and a pool's realized average price can differ by the
+//! pool's own slippage - that spread is the auction's price improvement,
+//! not tracked further here.
+
+use crate::amm::CFMM;
+use crate::market::OrderRouter;
+
+/// Number of bisection steps used to solve for the clearing price; each
+/// halves the bracket, so 60 steps narrows far past `f64` precision.
+const CLEARING_PRICE_ITERS: u32 = 60;
+
+/// A single AMM's contribution to a cleared batch.
+#[derive(Debug, Clone)]
+pub struct BatchFill {
+    pub amm_name: String,
+    pub amount_x: f64,
+    pub amount_y: f64,
+}
+
+impl OrderRouter {
+    /// Clear net demand (`net_buy_x` taker buys of X, `net_sell_x` taker
+    /// sells of X) against `amms` at a single uniform clearing price.
+    /// Returns the per-AMM fills and that clearing price.
+    ///
+    /// When `net_buy_x == net_sell_x`, the flow is fully internally crossed
+    /// and never touches the AMMs; the clearing price reported is the
+    /// depth-weighted spot the crossed volume settled at.
+    pub fn route_batch_auction(
+        &self,
+        net_buy_x: f64,
+        net_sell_x: f64,
+        amms: &mut [CFMM],
+        timestamp: u64,
+    ) -> (Vec<BatchFill>, Option<f64>) {
+        let net_x = net_buy_x - net_sell_x;
+        let total_depth: f64 = amms.iter().map(|amm| amm.reserves().0).sum();
+        if total_depth <= 0.0 {
+            return (Vec::new(), None);
+        }
+
+        if net_x == 0.0 {
+            let weighted_spot =
+                amms.iter().map(|amm| spot_price(amm) * amm.reserves().0).sum::<f64>() / total_depth;
+            return (Vec::new(), Some(weighted_spot));
+        }
+
+        let taker_buys_x = net_x > 0.0;
+        let Some(clearing_price) = solve_clearing_price(amms, net_x.abs(), taker_buys_x) else {
+            return (Vec::new(), None);
+        };
+
+        let mut fills = Vec::with_capacity(amms.len());
+        for amm in amms.iter_mut() {
+            let amm_x = trade_size_to_price(amm, clearing_price, taker_buys_x);
+            if amm_x <= 0.0 {
+                continue;
+            }
+
+            let (amount_y, filled) = if taker_buys_x {
+                // Net buyers: takers pull X out of the pools.
+                let (y, _) = amm.quote_sell_x(amm_x);
+                (y, amm.execute_sell_x(amm_x, timestamp))
+            } else {
+                let (y, _) = amm.quote_buy_x(amm_x);
+                (y, amm.execute_buy_x(amm_x, timestamp))
+            };
+
+            if filled.is_none() {
+                continue;
+            }
+
+            fills.push(BatchFill {
+                amm_name: amm.name.clone(),
+                amount_x: amm_x,
+                amount_y,
+            });
+        }
+
+        (fills, Some(clearing_price))
+    }
+}
+
+fn spot_price(amm: &CFMM) -> f64 {
+    let (rx, ry) = amm.reserves();
+    ry / rx
+}
+
+/// Size of the trade that moves `amm`'s marginal price to `price`, using the
+/// same fee-adjusted closed form as the arbitrageur's sizing.
+fn trade_size_to_price(amm: &CFMM, price: f64, taker_buys_x: bool) -> f64 {
+    let (rx, ry) = amm.reserves();
+    let k = rx * ry;
+    if taker_buys_x {
+        let fee = amm.fees().ask_fee.to_f64();
+        let new_x = (k * (1.0 + fee) / price).sqrt();
+        // Cap at 99% of reserves, mirroring Arbitrageur::quote_buy_arb: a
+        // clearing price far from this pool's spot shouldn't be able to
+        // drain it to (near) zero X.
+        (rx - new_x).max(0.0).min(rx * 0.99)
+    } else {
+        let fee = amm.fees().bid_fee.to_f64();
+        let new_x = (k * (1.0 - fee) / price).sqrt();
+        (new_x - rx).max(0.0)
+    }
+}
+
+/// Bisect for the price at which the AMMs' combined trade size (each sized
+/// to move its own marginal price to that candidate) equals `target`. Trade
+/// size is monotonic in price moving away from each pool's own spot in the
+/// taker's direction, so the combined size is too.
+fn solve_clearing_price(amms: &[CFMM], target: f64, taker_buys_x: bool) -> Option<f64> {
+    let size_at = |price: f64| -> f64 { amms.iter().map(|amm| trade_size_to_price(amm, price, taker_buys_x)).sum() };
+
+    let anchor = if taker_buys_x {
+        amms.iter().map(spot_price).fold(f64::INFINITY, f64::min)
+    } else {
+        amms.iter().map(spot_price).fold(f64::NEG_INFINITY, f64::max)
+    };
+
+    let mut far = anchor;
+    let step = if taker_buys_x { 1.01 } else { 1.0 / 1.01 };
+    for _ in 0..400 {
+        if size_at(far) >= target {
+            break;
+        }
+        far *= step;
+    }
+    if size_at(far) < target {
+        // The pools can never supply this much depth at any price.
+        return None;
+    }
+
+    let (mut lo, mut hi) = if taker_buys_x { (anchor, far) } else { (far, anchor) };
+    for _ in 0..CLEARING_PRICE_ITERS {
+        let mid = (lo + hi) / 2.0;
+        let under_target = size_at(mid) < target;
+        if under_target == taker_buys_x {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some((lo + hi) / 2.0)
+}
@@ -0,0 +1,123 @@
+//! Hybrid AMM+CLOB routing: split a retail order across AMMs and the
+//! central limit order book to minimize total execution cost.
+//!
+//! For each order, [`OrderRouter::route_hybrid`] compares the marginal
+//! execution price available from each AMM against the top of the order
+//! book and splits the order into `ROUTING_SLICES` equal slices, placing
+//! each slice on whichever venue is cheapest at that moment, so the venues'
+//! marginal prices move toward each other as the order fills.
+
+use crate::amm::CFMM;
+use crate::market::orderbook::{OrderBook, Side};
+use crate::market::OrderRouter;
+
+/// How much of a routed order one venue filled.
+#[derive(Debug, Clone)]
+pub struct VenueFill {
+    /// `"book"` or the AMM's name.
+    pub venue: String,
+    pub amount_x: f64,
+    pub amount_y: f64,
+}
+
+/// Number of slices an order is split into while probing marginal prices.
+/// Finer slicing converges closer to the true equal-marginal-price split
+/// at the cost of more quote calls per order.
+const ROUTING_SLICES: u32 = 20;
+
+impl OrderRouter {
+    /// Route a single retail order of `amount_x` across `amms` and `book`,
+    /// splitting it slice-by-slice onto whichever venue currently offers
+    /// the best marginal price for the taker.
+    pub fn route_hybrid(
+        &self,
+        side: Side,
+        amount_x: f64,
+        amms: &mut [CFMM],
+        book: &mut OrderBook,
+        timestamp: u64,
+    ) -> Vec<VenueFill> {
+        let mut fills: std::collections::HashMap<String, VenueFill> = std::collections::HashMap::new();
+        let slice_x = amount_x / ROUTING_SLICES as f64;
+        if slice_x <= 0.0 {
+            return Vec::new();
+        }
+
+        for _ in 0..ROUTING_SLICES {
+            let amm_marginal: Vec<(usize, f64)> = amms
+                .iter()
+                .enumerate()
+                .map(|(i, amm)| {
+                    let price = match side {
+                        Side::Buy => amm.quote_sell_x(slice_x).0 / slice_x,
+                        Side::Sell => amm.quote_buy_x(slice_x).0 / slice_x,
+                    };
+                    (i, price)
+                })
+                .collect();
+
+            let book_price = match side {
+                // A taker buying X lifts the best ask; a taker selling X
+                // hits the best bid.
+                Side::Buy => book.best_ask(),
+                Side::Sell => book.best_bid(),
+            };
+
+            // Buying X wants the venue charging the lowest price per X; selling
+            // X wants the venue paying the highest price per X.
+            let best_amm = match side {
+                Side::Buy => amm_marginal.iter().cloned().min_by(|a, b| a.1.partial_cmp(&b.1).unwrap()),
+                Side::Sell => amm_marginal.iter().cloned().max_by(|a, b| a.1.partial_cmp(&b.1).unwrap()),
+            };
+
+            let use_book = match (best_amm, book_price) {
+                (Some((_, amm_price)), Some(book_price)) => match side {
+                    Side::Buy => book_price <= amm_price,
+                    Side::Sell => book_price >= amm_price,
+                },
+                (None, Some(_)) => true,
+                _ => false,
+            };
+
+            if use_book {
+                let book_side = match side {
+                    Side::Buy => Side::Buy,
+                    Side::Sell => Side::Sell,
+                };
+                let venue_fills = book.match_market_order(book_side, slice_x);
+                if venue_fills.is_empty() {
+                    continue;
+                }
+                let (fx, fy) = venue_fills
+                    .iter()
+                    .fold((0.0, 0.0), |(ax, ay), f| (ax + f.quantity, ay + f.quantity * f.price));
+                let entry = fills
+                    .entry("book".to_string())
+                    .or_insert_with(|| VenueFill { venue: "book".to_string(), amount_x: 0.0, amount_y: 0.0 });
+                entry.amount_x += fx;
+                entry.amount_y += fy;
+            } else if let Some((i, quoted_y)) = best_amm {
+                let amm = &mut amms[i];
+                let amount_y = quoted_y * slice_x;
+                let filled = match side {
+                    Side::Buy => amm.execute_sell_x(slice_x, timestamp),
+                    Side::Sell => amm.execute_buy_x(slice_x, timestamp),
+                };
+                if filled.is_none() {
+                    continue;
+                }
+                let entry = fills.entry(amm.name.clone()).or_insert_with(|| VenueFill {
+                    venue: amm.name.clone(),
+                    amount_x: 0.0,
+                    amount_y: 0.0,
+                });
+                entry.amount_x += slice_x;
+                entry.amount_y += amount_y;
+            } else {
+                break;
+            }
+        }
+
+        fills.into_values().collect()
+    }
+}
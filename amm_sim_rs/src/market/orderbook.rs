@@ -0,0 +1,179 @@
+//! Central limit order book venue: price-time priority matching for resting
+//! limit orders and market orders.
+
+use std::collections::VecDeque;
+
+/// Order side from the taker's perspective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// A resting limit order in the book.
+#[derive(Debug, Clone)]
+pub struct LimitOrder {
+    pub id: u64,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+    pub timestamp: u64,
+}
+
+/// A single fill produced by matching a market order against the book.
+#[derive(Debug, Clone)]
+pub struct Fill {
+    pub price: f64,
+    pub quantity: f64,
+    pub resting_order_id: u64,
+}
+
+/// Central limit order book with strict price-time priority.
+///
+/// Bids are kept sorted best-first (highest price, then earliest
+/// timestamp); asks are kept sorted best-first (lowest price, then
+/// earliest timestamp).
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    bids: VecDeque<LimitOrder>,
+    asks: VecDeque<LimitOrder>,
+    next_id: u64,
+}
+
+impl OrderBook {
+    /// Create an empty book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Best (highest) resting bid price.
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.front().map(|o| o.price)
+    }
+
+    /// Best (lowest) resting ask price.
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.front().map(|o| o.price)
+    }
+
+    /// Mid price, if both sides have resting liquidity.
+    pub fn mid_price(&self) -> Option<f64> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(b), Some(a)) => Some((a + b) / 2.0),
+            _ => None,
+        }
+    }
+
+    /// Rest a new limit order, inserting it to maintain price-time priority.
+    pub fn add_limit_order(&mut self, side: Side, price: f64, quantity: f64, timestamp: u64) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let order = LimitOrder { id, side, price, quantity, timestamp };
+
+        let book_side = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        let pos = book_side
+            .iter()
+            .position(|resting| match side {
+                Side::Buy => resting.price < price,
+                Side::Sell => resting.price > price,
+            })
+            .unwrap_or(book_side.len());
+        book_side.insert(pos, order);
+        id
+    }
+
+    /// Quote a market order against resting opposing levels without
+    /// mutating the book, mirroring `match_market_order`'s price-time walk.
+    /// Returns the quantity that could actually be filled and its notional.
+    pub fn quote_market_order(&self, side: Side, quantity: f64) -> (f64, f64) {
+        let opposing = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+
+        let mut remaining = quantity;
+        let mut filled = 0.0;
+        let mut notional = 0.0;
+        for resting in opposing.iter() {
+            if remaining <= 0.0 {
+                break;
+            }
+            let fill_qty = remaining.min(resting.quantity);
+            filled += fill_qty;
+            notional += fill_qty * resting.price;
+            remaining -= fill_qty;
+        }
+        (filled, notional)
+    }
+
+    /// Fill a market order against resting opposing levels, crossing the
+    /// book best-level-first until `quantity` is exhausted or liquidity
+    /// runs out.
+    pub fn match_market_order(&mut self, side: Side, mut quantity: f64) -> Vec<Fill> {
+        let opposing = match side {
+            Side::Buy => &mut self.asks,
+            Side::Sell => &mut self.bids,
+        };
+
+        let mut fills = Vec::new();
+        while quantity > 0.0 {
+            let Some(resting) = opposing.front_mut() else {
+                break;
+            };
+            let fill_qty = quantity.min(resting.quantity);
+            fills.push(Fill {
+                price: resting.price,
+                quantity: fill_qty,
+                resting_order_id: resting.id,
+            });
+            resting.quantity -= fill_qty;
+            quantity -= fill_qty;
+            if resting.quantity <= 0.0 {
+                opposing.pop_front();
+            }
+        }
+        fills
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_priority() {
+        let mut book = OrderBook::new();
+        book.add_limit_order(Side::Sell, 101.0, 5.0, 0);
+        book.add_limit_order(Side::Sell, 100.0, 5.0, 1);
+        assert_eq!(book.best_ask(), Some(100.0));
+    }
+
+    #[test]
+    fn test_quote_market_order_does_not_mutate_book() {
+        let mut book = OrderBook::new();
+        book.add_limit_order(Side::Sell, 100.0, 4.0, 0);
+
+        let (filled, notional) = book.quote_market_order(Side::Buy, 4.0);
+        assert_eq!(filled, 4.0);
+        assert_eq!(notional, 400.0);
+        assert_eq!(book.best_ask(), Some(100.0));
+    }
+
+    #[test]
+    fn test_market_order_crosses_multiple_levels() {
+        let mut book = OrderBook::new();
+        book.add_limit_order(Side::Sell, 100.0, 4.0, 0);
+        book.add_limit_order(Side::Sell, 101.0, 4.0, 1);
+
+        let fills = book.match_market_order(Side::Buy, 6.0);
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].price, 100.0);
+        assert_eq!(fills[0].quantity, 4.0);
+        assert_eq!(fills[1].price, 101.0);
+        assert_eq!(fills[1].quantity, 2.0);
+        assert_eq!(book.best_ask(), Some(101.0));
+    }
+}
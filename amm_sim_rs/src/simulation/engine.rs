@@ -2,17 +2,25 @@
 
 use std::collections::HashMap;
 
+use crate::amm::gamma::instantaneous_lvr;
 use crate::amm::CFMM;
+use crate::analytics::compute_strategy_metrics;
 use crate::evm::EVMStrategy;
+use crate::market::arbitrageur::Pool;
+use crate::market::orderbook::{OrderBook, Side};
 use crate::market::{Arbitrageur, GBMPriceProcess, OrderRouter, RetailTrader};
 use crate::types::config::SimulationConfig;
 use crate::types::result::{LightweightSimResult, LightweightStepResult};
+use crate::types::routing_mode::RoutingMode;
 
 /// Error type for simulation.
 #[derive(Debug)]
 pub enum SimulationError {
     EVMError(String),
     InvalidConfig(String),
+    /// A fixed-point accounting step overflowed instead of silently
+    /// producing `inf`/`NaN`.
+    Overflow(String),
 }
 
 impl std::fmt::Display for SimulationError {
@@ -20,18 +28,28 @@ impl std::fmt::Display for SimulationError {
         match self {
             SimulationError::EVMError(s) => write!(f, "EVM error: {}", s),
             SimulationError::InvalidConfig(s) => write!(f, "Invalid config: {}", s),
+            SimulationError::Overflow(s) => write!(f, "fixed-point overflow: {}", s),
         }
     }
 }
 
 impl std::error::Error for SimulationError {}
 
+impl From<crate::types::fixed::FixedPointError> for SimulationError {
+    fn from(err: crate::types::fixed::FixedPointError) -> Self {
+        SimulationError::Overflow(err.to_string())
+    }
+}
+
 /// Main simulation engine for AMM competition.
 ///
 /// Runs a simulation with the following loop per step:
-/// 1. Generate new fair price via GBM
-/// 2. Arbitrageur extracts profit from each AMM
-/// 3. Retail orders arrive and are routed to best AMM
+/// 1. Generate new fair price via GBM, then refresh the book's resting
+///    liquidity around it
+/// 2. Arbitrageur extracts profit from each AMM, and from the AMM-vs-book
+///    spread
+/// 3. Retail orders arrive and are routed per `RoutingMode` (best AMM,
+///    batch-auction clearing, or split across AMMs and the book)
 pub struct SimulationEngine {
     config: SimulationConfig,
 }
@@ -68,8 +86,13 @@ impl SimulationEngine {
             Some(seed + 1),
         );
 
-        let arbitrageur = Arbitrageur::new();
+        let arbitrageur = Arbitrageur::new(self.config.min_trade_size_y);
         let router = OrderRouter::new();
+        // Resting book liquidity is refreshed every step (see
+        // `seed_book_liquidity`) as a two-sided quote straddling the fair
+        // price, so the arb-vs-book and Hybrid routing paths always have
+        // something to trade against.
+        let mut book = OrderBook::new();
 
         // Create AMMs with fixed positional names to avoid HashMap collision
         // when both contracts return the same getName()
@@ -123,39 +146,120 @@ impl SimulationEngine {
         // Track cumulative volumes
         let mut arb_volume_y: HashMap<String, f64> = HashMap::new();
         let mut retail_volume_y: HashMap<String, f64> = HashMap::new();
+        let mut cumulative_lvr: HashMap<String, f64> = HashMap::new();
+        let mut book_fill_volume_y: f64 = 0.0;
+        let mut book_fill_count: u64 = 0;
         for name in &names {
             arb_volume_y.insert(name.clone(), 0.0);
             retail_volume_y.insert(name.clone(), 0.0);
+            cumulative_lvr.insert(name.clone(), 0.0);
         }
 
         for t in 0..self.config.n_steps {
             // 1. Generate new fair price
             let fair_price = price_process.step();
 
+            // 1b. Accrue theoretical no-fee LVR against the new fair price
+            let mut step_lvr: HashMap<String, f64> = HashMap::new();
+            for (amm, name) in amms.iter().zip(names.iter()) {
+                let lvr = instantaneous_lvr(amm, fair_price, self.config.gbm_sigma) * self.config.gbm_dt;
+                *cumulative_lvr.get_mut(name).unwrap() += lvr;
+                step_lvr.insert(name.clone(), lvr);
+            }
+
+            // 1c. Refresh the book's resting liquidity around the new fair
+            // price so the arb-vs-book and Hybrid routing paths have
+            // something to trade against this step.
+            seed_book_liquidity(&mut book, fair_price, t as u64);
+
             // 2. Arbitrageur extracts profit from each AMM
             for amm in amms.iter_mut() {
-                if let Some(arb_result) = arbitrageur.execute_arb(amm, fair_price, t as u64) {
+                if let Some(arb_result) = arbitrageur.execute_arb(Pool::ConstantProduct(amm), fair_price, t as u64)? {
                     *arb_volume_y.get_mut(&arb_result.amm_name).unwrap() += arb_result.amount_y;
                     let entry = markouts.entry(arb_result.amm_name).or_insert(0.0);
                     // AMM markout is the negative of arbitrageur profit at true price
                     *entry += -arb_result.profit;
                 }
-            }
 
-            // 3. Retail orders arrive and get routed
-            let orders = retail_trader.generate_orders();
-            let routed_trades = router.route_orders(&orders, &mut amms, fair_price, t as u64);
-            for trade in routed_trades {
-                *retail_volume_y.get_mut(&trade.amm_name).unwrap() += trade.amount_y;
-                let trade_markout = if trade.amm_buys_x {
-                    trade.amount_x * fair_price - trade.amount_y
-                } else {
-                    trade.amount_y - trade.amount_x * fair_price
-                };
-                let entry = markouts.entry(trade.amm_name).or_insert(0.0);
-                *entry += trade_markout;
+                // 2b. Arb the AMM against the order book when its spot
+                // price diverges from the book's mid.
+                if let Some(book_arb) = arbitrageur.execute_arb_vs_book(amm, &mut book, t as u64)? {
+                    book_fill_volume_y += book_arb.amount_y;
+                    book_fill_count += 1;
+                    let entry = markouts.entry(book_arb.amm_name).or_insert(0.0);
+                    *entry += -book_arb.profit;
+                }
             }
 
+            // 3. Retail orders arrive and get routed, dropping dust orders
+            // whose notional falls below the configured threshold
+            let mut orders = retail_trader.generate_orders();
+            orders.retain(|order| order.amount_x * fair_price >= self.config.min_trade_size_y);
+            let clearing_price = match self.config.routing_mode {
+                RoutingMode::Greedy => {
+                    let routed_trades = router.route_orders(&orders, &mut amms, fair_price, t as u64);
+                    for trade in routed_trades {
+                        *retail_volume_y.get_mut(&trade.amm_name).unwrap() += trade.amount_y;
+                        let trade_markout = if trade.amm_buys_x {
+                            trade.amount_x * fair_price - trade.amount_y
+                        } else {
+                            trade.amount_y - trade.amount_x * fair_price
+                        };
+                        let entry = markouts.entry(trade.amm_name).or_insert(0.0);
+                        *entry += trade_markout;
+                    }
+                    None
+                }
+                RoutingMode::BatchAuction => {
+                    let (net_buy_x, net_sell_x) = orders.iter().fold((0.0, 0.0), |(buy, sell), order| {
+                        if order.is_buy {
+                            (buy + order.amount_x, sell)
+                        } else {
+                            (buy, sell + order.amount_x)
+                        }
+                    });
+                    let (fills, clearing_price) =
+                        router.route_batch_auction(net_buy_x, net_sell_x, &mut amms, t as u64);
+                    for fill in fills {
+                        *retail_volume_y.get_mut(&fill.amm_name).unwrap() += fill.amount_y;
+                        let fill_markout = if net_buy_x >= net_sell_x {
+                            // Net buyers: takers pull X out of the pools (AMM sells X).
+                            fill.amount_y - fill.amount_x * fair_price
+                        } else {
+                            // Net sellers: takers push X into the pools (AMM buys X).
+                            fill.amount_x * fair_price - fill.amount_y
+                        };
+                        let entry = markouts.entry(fill.amm_name).or_insert(0.0);
+                        *entry += fill_markout;
+                    }
+                    clearing_price
+                }
+                RoutingMode::Hybrid => {
+                    for order in &orders {
+                        let side = if order.is_buy { Side::Buy } else { Side::Sell };
+                        let fills = router.route_hybrid(side, order.amount_x, &mut amms, &mut book, t as u64);
+                        for fill in fills {
+                            if fill.venue == "book" {
+                                book_fill_volume_y += fill.amount_y;
+                                book_fill_count += 1;
+                            } else {
+                                *retail_volume_y.entry(fill.venue.clone()).or_insert(0.0) += fill.amount_y;
+                            }
+                            let fill_markout = if order.is_buy {
+                                // Taker buys X: the venue sells X.
+                                fill.amount_y - fill.amount_x * fair_price
+                            } else {
+                                // Taker sells X: the venue buys X.
+                                fill.amount_x * fair_price - fill.amount_y
+                            };
+                            let entry = markouts.entry(fill.venue).or_insert(0.0);
+                            *entry += fill_markout;
+                        }
+                    }
+                    None
+                }
+            };
+
             // 4. Capture step result
             let step = capture_step(
                 t,
@@ -164,6 +268,8 @@ impl SimulationEngine {
                 &names,
                 &initial_reserves,
                 initial_fair_price,
+                &step_lvr,
+                clearing_price,
             );
             steps.push(step);
         }
@@ -183,6 +289,14 @@ impl SimulationEngine {
             pnl.insert(name.clone(), final_value - init_value);
         }
 
+        let strategy_metrics = compute_strategy_metrics(
+            &steps,
+            &names,
+            self.config.gbm_dt,
+            &arb_volume_y,
+            &retail_volume_y,
+        );
+
         Ok(LightweightSimResult {
             seed,
             strategies: vec![submission_name, baseline_name],
@@ -193,10 +307,32 @@ impl SimulationEngine {
             steps,
             arb_volume_y,
             retail_volume_y,
+            cumulative_lvr,
+            book_fill_volume_y,
+            book_fill_count,
+            strategy_metrics,
         })
     }
 }
 
+/// Width (as a fraction of fair price) of the resting quote on each side of
+/// the book's refreshed market-making spread.
+const BOOK_MAKER_SPREAD: f64 = 0.001;
+/// Depth (in X) rested on each side of the book every step.
+const BOOK_MAKER_DEPTH_X: f64 = 50.0;
+
+/// Replace the book's resting liquidity with a fresh two-sided quote
+/// straddling `fair_price`, so the arb-vs-book and Hybrid routing paths
+/// always have something to trade against. A real deployment would seed
+/// this from an actual market-making strategy; this is a simple symmetric
+/// stand-in.
+fn seed_book_liquidity(book: &mut OrderBook, fair_price: f64, timestamp: u64) {
+    *book = OrderBook::new();
+    let half_spread = fair_price * BOOK_MAKER_SPREAD;
+    book.add_limit_order(Side::Buy, fair_price - half_spread, BOOK_MAKER_DEPTH_X, timestamp);
+    book.add_limit_order(Side::Sell, fair_price + half_spread, BOOK_MAKER_DEPTH_X, timestamp);
+}
+
 fn capture_step(
     timestamp: u32,
     fair_price: f64,
@@ -204,6 +340,8 @@ fn capture_step(
     names: &[String],
     initial_reserves: &HashMap<String, (f64, f64)>,
     initial_fair_price: f64,
+    lvr: &HashMap<String, f64>,
+    clearing_price: Option<f64>,
 ) -> LightweightStepResult {
     let mut spot_prices = HashMap::new();
     let mut pnls = HashMap::new();
@@ -235,6 +373,8 @@ fn capture_step(
         spot_prices,
         pnls,
         fees,
+        lvr: lvr.clone(),
+        clearing_price,
     }
 }
 
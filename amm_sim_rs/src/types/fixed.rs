@@ -0,0 +1,318 @@
+//! Deterministic Q64.64 fixed-point arithmetic.
+//!
+//! `f64` reserves and the `sqrt` in the arbitrage closed form make
+//! simulation results non-reproducible across platforms: operation ordering
+//! and FMA use can make the same `seed` yield different low-order bits of
+//! `pnl` on different machines. `Fixed64` runs the arbitrage sizing formula
+//! (the `k`, fee-adjusted target and `sqrt` in
+//! [`Arbitrageur`](crate::market::arbitrageur::Arbitrageur)'s closed form) in
+//! checked 128-bit fixed-point so that a given `seed` replays to a
+//! bit-identical trade size on any machine. AMM reserves, pricing, fees and
+//! quoting still run in `f64`, since they live in `CFMM` outside this
+//! module; only the sizing step is covered.
+
+/// A signed Q64.64 fixed-point number: 64 integer bits, 64 fractional bits,
+/// backed by `i128`. Multiplication, division and square root all widen
+/// into 256-bit intermediates before rescaling back down, so a raw value
+/// near `i128::MAX` never silently wraps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed64(i128);
+
+const FRACTIONAL_BITS: u32 = 64;
+
+/// Error returned by checked `Fixed64` arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedPointError {
+    /// The operation's result does not fit in `Fixed64`.
+    Overflow,
+    /// Division by zero.
+    DivisionByZero,
+}
+
+impl std::fmt::Display for FixedPointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FixedPointError::Overflow => write!(f, "fixed-point overflow"),
+            FixedPointError::DivisionByZero => write!(f, "fixed-point division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for FixedPointError {}
+
+impl Fixed64 {
+    pub const ZERO: Fixed64 = Fixed64(0);
+    pub const ONE: Fixed64 = Fixed64(1 << FRACTIONAL_BITS);
+
+    /// Construct from a raw Q64.64 integer representation.
+    pub const fn from_raw(raw: i128) -> Self {
+        Self(raw)
+    }
+
+    /// Raw Q64.64 integer representation.
+    pub const fn raw(self) -> i128 {
+        self.0
+    }
+
+    /// Convert from `f64` at the entry of the fixed-point sizing step (the
+    /// arbitrageur converts reserves, fee factor and fair price on every
+    /// sizing call).
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * (1u128 << FRACTIONAL_BITS) as f64) as i128)
+    }
+
+    /// Convert back to `f64` at the exit of the fixed-point sizing step.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1u128 << FRACTIONAL_BITS) as f64
+    }
+
+    /// Checked addition.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, FixedPointError> {
+        self.0.checked_add(rhs.0).map(Self).ok_or(FixedPointError::Overflow)
+    }
+
+    /// Checked subtraction.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, FixedPointError> {
+        self.0.checked_sub(rhs.0).map(Self).ok_or(FixedPointError::Overflow)
+    }
+
+    /// Checked multiplication, rescaling back down to Q64.64.
+    ///
+    /// The raw product of two Q64.64 values is scaled by `2^128`, which
+    /// overflows `i128` for most non-trivial magnitudes (anything with
+    /// `a*b >~ 0.5`). The multiply is done in a widening 256-bit
+    /// intermediate and only the rescaled result is checked against
+    /// `Fixed64`'s range.
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, FixedPointError> {
+        let negative = (self.0 < 0) != (rhs.0 < 0);
+        let (hi, lo) = widening_mul_u128(self.0.unsigned_abs(), rhs.0.unsigned_abs());
+
+        // Product is in Q128.128; rescale to Q64.64 by shifting the 256-bit
+        // value right by `FRACTIONAL_BITS`.
+        if hi >> FRACTIONAL_BITS != 0 {
+            return Err(FixedPointError::Overflow);
+        }
+        let magnitude = (hi << FRACTIONAL_BITS) | (lo >> FRACTIONAL_BITS);
+        to_signed(magnitude, negative)
+    }
+
+    /// Checked division, scaling up before dividing to preserve precision.
+    ///
+    /// Naively shifting the dividend left by `FRACTIONAL_BITS` in `i128`
+    /// overflows (and `checked_shl` doesn't catch it, since it only guards
+    /// the shift *amount*, not the value) for any `|self| >= 0.5`. The
+    /// dividend is widened into a 256-bit intermediate before the shift so
+    /// no bits are lost.
+    pub fn checked_div(self, rhs: Self) -> Result<Self, FixedPointError> {
+        if rhs.0 == 0 {
+            return Err(FixedPointError::DivisionByZero);
+        }
+        let negative = (self.0 < 0) != (rhs.0 < 0);
+        let (hi, lo) = widen_by_fractional_bits(self.0.unsigned_abs());
+        let (quotient, _remainder) =
+            div_u256_by_u128(hi, lo, rhs.0.unsigned_abs()).ok_or(FixedPointError::Overflow)?;
+        to_signed(quotient, negative)
+    }
+
+    /// Integer square root, rounded down to the Q64.64 unit. Errors on
+    /// negative operands.
+    ///
+    /// `sqrt(x)` in Q64.64 is `sqrt(raw << FRACTIONAL_BITS)` scaled back
+    /// down, and that shift overflows `u128` for any `self >= 1.0`. The
+    /// radicand is widened into a 256-bit intermediate first, and the root
+    /// is found with Newton's method over that wide representation.
+    pub fn checked_sqrt(self) -> Result<Self, FixedPointError> {
+        if self.0 < 0 {
+            return Err(FixedPointError::Overflow);
+        }
+        if self.0 == 0 {
+            return Ok(Self::ZERO);
+        }
+
+        let (hi, lo) = widen_by_fractional_bits(self.0 as u128);
+        Ok(Self(isqrt_u256(hi, lo) as i128))
+    }
+}
+
+/// Widen `value` by `FRACTIONAL_BITS`, i.e. compute `value << FRACTIONAL_BITS`
+/// as a 256-bit unsigned integer split into `(hi, lo)` where
+/// `value << FRACTIONAL_BITS == hi * 2^128 + lo`.
+fn widen_by_fractional_bits(value: u128) -> (u128, u128) {
+    let hi = value >> FRACTIONAL_BITS;
+    let lo = (value & (u64::MAX as u128)) << FRACTIONAL_BITS;
+    (hi, lo)
+}
+
+/// Convert a `(magnitude, negative)` pair back into `Fixed64`, erroring if
+/// the magnitude doesn't fit in `i128`.
+fn to_signed(magnitude: u128, negative: bool) -> Result<Fixed64, FixedPointError> {
+    let max_magnitude = if negative { i128::MIN.unsigned_abs() } else { i128::MAX as u128 };
+    if magnitude > max_magnitude {
+        return Err(FixedPointError::Overflow);
+    }
+    if negative {
+        if magnitude == i128::MIN.unsigned_abs() {
+            return Ok(Fixed64(i128::MIN));
+        }
+        Ok(Fixed64(-(magnitude as i128)))
+    } else {
+        Ok(Fixed64(magnitude as i128))
+    }
+}
+
+/// Full 128x128 -> 256-bit unsigned multiplication, returned as `(hi, lo)`
+/// such that `a * b == hi * 2^128 + lo`.
+fn widening_mul_u128(a: u128, b: u128) -> (u128, u128) {
+    let mask = u64::MAX as u128;
+    let a_lo = a & mask;
+    let a_hi = a >> 64;
+    let b_lo = b & mask;
+    let b_hi = b >> 64;
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let cross = hi_lo + (lo_lo >> 64) + (lo_hi & mask);
+    let carry = cross >> 64;
+    let low = (lo_lo & mask) | ((cross & mask) << 64);
+    let high = hi_hi + (lo_hi >> 64) + carry;
+
+    (high, low)
+}
+
+/// Divide a 256-bit unsigned dividend `hi * 2^128 + lo` by a `u128` divisor
+/// via schoolbook binary long division. Returns `(quotient, remainder)`, or
+/// `None` if `divisor == 0` or the quotient doesn't fit in a `u128`.
+fn div_u256_by_u128(hi: u128, lo: u128, divisor: u128) -> Option<(u128, u128)> {
+    if divisor == 0 {
+        return None;
+    }
+
+    // `divisor` is at most 2^127 here (an `i128::unsigned_abs`, or a square
+    // root estimate far below that), so `remainder` (always < `divisor`)
+    // never overflows on the `<< 1` below.
+    let mut remainder: u128 = 0;
+    for i in (0..128).rev() {
+        remainder = (remainder << 1) | ((hi >> i) & 1);
+        if remainder >= divisor {
+            // This bit belongs at position 128+i or higher in the quotient,
+            // which doesn't fit in a `u128` result.
+            return None;
+        }
+    }
+
+    let mut quotient: u128 = 0;
+    for i in (0..128).rev() {
+        remainder = (remainder << 1) | ((lo >> i) & 1);
+        if remainder >= divisor {
+            remainder -= divisor;
+            quotient |= 1 << i;
+        }
+    }
+
+    Some((quotient, remainder))
+}
+
+/// Integer square root of the 256-bit unsigned value `hi * 2^128 + lo`,
+/// via Newton's method seeded from an `f64` approximation and refined with
+/// exact wide arithmetic.
+fn isqrt_u256(hi: u128, lo: u128) -> u128 {
+    if hi == 0 && lo == 0 {
+        return 0;
+    }
+
+    let approx = (hi as f64) * 2f64.powi(128) + (lo as f64);
+    let mut x = (approx.sqrt() as u128).max(1);
+    // From any positive seed, the first Newton step lands at or above the
+    // floor root; only later steps stop once the estimate stops descending.
+    let mut first_step = true;
+
+    loop {
+        let Some((q, _)) = div_u256_by_u128(hi, lo, x) else {
+            // `x` undershoots the true root badly enough that `value / x`
+            // doesn't fit in a `u128`; grow it and retry.
+            x = x.saturating_mul(2).max(1);
+            continue;
+        };
+        let next = (x + q) / 2;
+        if next >= x && !first_step {
+            break;
+        }
+        first_step = false;
+        x = next;
+    }
+
+    // Newton's method above converges from above; step to the exact floor.
+    while {
+        let (sq_hi, sq_lo) = widening_mul_u128(x, x);
+        sq_hi > hi || (sq_hi == hi && sq_lo > lo)
+    } {
+        x -= 1;
+    }
+    while {
+        let (sq_hi, sq_lo) = widening_mul_u128(x + 1, x + 1);
+        sq_hi < hi || (sq_hi == hi && sq_lo <= lo)
+    } {
+        x += 1;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_f64() {
+        let a = Fixed64::from_f64(1234.5);
+        assert!((a.to_f64() - 1234.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_checked_mul_div_identity() {
+        let a = Fixed64::from_f64(3.0);
+        let b = Fixed64::from_f64(4.0);
+        let product = a.checked_mul(b).unwrap();
+        assert!((product.to_f64() - 12.0).abs() < 1e-9);
+
+        let quotient = product.checked_div(b).unwrap();
+        assert!((quotient.to_f64() - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_checked_mul_handles_realistic_reserves() {
+        let rx = Fixed64::from_f64(1000.0);
+        let ry = Fixed64::from_f64(1000.0);
+        let k = rx.checked_mul(ry).unwrap();
+        assert!((k.to_f64() - 1_000_000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_checked_sqrt() {
+        let a = Fixed64::from_f64(9.0);
+        let root = a.checked_sqrt().unwrap();
+        assert!((root.to_f64() - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_checked_sqrt_above_one() {
+        let a = Fixed64::from_f64(1_000_000.0);
+        let root = a.checked_sqrt().unwrap();
+        assert!((root.to_f64() - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_div_by_zero_errs() {
+        let a = Fixed64::from_f64(1.0);
+        assert_eq!(a.checked_div(Fixed64::ZERO), Err(FixedPointError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_mul_overflow_errs() {
+        let huge = Fixed64::from_raw(i128::MAX);
+        assert_eq!(huge.checked_mul(huge), Err(FixedPointError::Overflow));
+    }
+}
@@ -0,0 +1,18 @@
+//! Retail order routing strategy selector.
+
+/// How retail order flow is routed across AMMs each step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoutingMode {
+    /// Route each order individually to whichever AMM offers the best
+    /// marginal price at the time it is processed - the original behavior.
+    /// Whichever AMM is quoted first has a within-step ordering advantage.
+    #[default]
+    Greedy,
+    /// Aggregate all of a step's orders into net demand and clear them all
+    /// at a single uniform price, removing that ordering advantage.
+    BatchAuction,
+    /// Split each order slice-by-slice across the AMMs and the resting
+    /// order book, taking whichever venue currently offers the best
+    /// marginal price.
+    Hybrid,
+}